@@ -0,0 +1,144 @@
+//! A self-referential [`Future`] that borrows into its own buffer across
+//! `.await`, mirroring the `read_into_buf_fut` example from the async book's
+//! pinning chapter.
+//!
+//! The future owns a byte buffer and, on the first poll, records a raw pointer
+//! *into* that buffer. If the future were moved after that pointer is set the
+//! pointer would dangle, which is exactly why [`Future::poll`] takes
+//! `Pin<&mut Self>`.
+
+use std::{
+    future::Future,
+    marker::PhantomPinned,
+    pin::Pin,
+    ptr::null,
+    task::{Context, Poll},
+};
+
+/// A future that fills an owned `[u8; N]` buffer one byte per poll and reads it
+/// back through a pointer that refers into the future itself.
+#[derive(Debug)]
+pub struct SelfRefFuture<const N: usize> {
+    /// the buffer the self reference points into
+    buf: [u8; N],
+    /// a pointer to `buf`, set lazily on the first poll
+    ptr: *const [u8; N],
+    /// how many bytes have been "filled" so far
+    filled: usize,
+    /// make sure `buf` won't be moved once `ptr` is set
+    _pin: PhantomPinned,
+}
+impl<const N: usize> SelfRefFuture<N> {
+    /// Return a future with an empty buffer and no self reference yet.
+    pub fn new() -> Self {
+        Self {
+            buf: [0; N],
+            ptr: null(),
+            filled: 0,
+            _pin: PhantomPinned,
+        }
+    }
+}
+impl<const N: usize> Default for SelfRefFuture<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> Future for SelfRefFuture<N> {
+    /// The filled buffer, read back through the self reference.
+    type Output = [u8; N];
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: we never move out of `this`; we only mutate in place.
+        let this = unsafe { self.get_unchecked_mut() };
+
+        // On the first poll, wire up the self reference into our own buffer.
+        if this.ptr.is_null() {
+            this.ptr = &this.buf as _;
+        }
+
+        if this.filled < N {
+            this.buf[this.filled] = this.filled as u8;
+            this.filled += 1;
+            // Ask to be polled again to fill the next byte.
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        // Read the result back through the self reference rather than `buf`
+        // directly, exercising the pointer that refers into this future.
+        // SAFETY: `ptr` was set to `&this.buf` and the future has been pinned
+        // since before the pointer was established, so it is still valid.
+        Poll::Ready(*unsafe { this.ptr.as_ref() }.unwrap())
+    }
+}
+
+/// A minimal executor that drives a future to completion on the current thread,
+/// re-polling it in a busy loop until it is ready; wake-ups are ignored.
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    use std::task::{RawWaker, RawWakerVTable, Waker};
+
+    // A no-op waker: our `block_on` simply busy-polls, so waking is a no-op.
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    // SAFETY: the vtable only ever manipulates the unit data pointer.
+    let waker = unsafe { Waker::from_raw(RawWaker::new(null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+
+    let mut fut = Box::pin(fut);
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drives_to_completion() {
+        let out = block_on(SelfRefFuture::<4>::new());
+        assert_eq!(out, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_moving_before_first_poll_is_fine() {
+        // Before the first poll the self pointer is still null, so moving the
+        // future around is harmless — this is why the pointer is only set once
+        // the future is guaranteed to be pinned.
+        fn move_it<const N: usize>(fut: SelfRefFuture<N>) -> SelfRefFuture<N> {
+            fut
+        }
+
+        let fut = SelfRefFuture::<4>::new();
+        assert!(fut.ptr.is_null());
+        // Move it into a new slot; since the pointer is null nothing breaks.
+        let fut = move_it(fut);
+        assert!(fut.ptr.is_null());
+        // Polling from a pinned location still works correctly afterwards.
+        assert_eq!(block_on(fut), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_moving_after_pointer_set_dangles() {
+        // Set the self pointer by hand while the future sits unpinned on the
+        // stack, then move the future. The pointer now refers to the old,
+        // vacated slot rather than the moved-to buffer — demonstrating the
+        // dangling-pointer hazard that pinning exists to prevent.
+        let mut fut = SelfRefFuture::<4>::new();
+        // SAFETY (for the demonstration only): we deliberately set the pointer
+        // while unpinned so we can observe the bug that `Pin` forbids.
+        fut.ptr = &fut.buf as _;
+
+        let moved = fut;
+        // The recorded pointer no longer equals the address of the moved
+        // buffer: the self reference has been invalidated by the move.
+        assert_ne!(moved.ptr, &moved.buf as *const _);
+    }
+}
@@ -0,0 +1,115 @@
+//! An intrusive, self-referential doubly-linked list built directly on the pin
+//! invariant — the canonical motivating use case for `Pin`.
+//!
+//! Each [`PinnedNode`] stores raw `next`/`prev` pointers into its siblings.
+//! Those pointers are only ever wired up between nodes that are *already*
+//! pinned (every linking API takes `Pin<&mut PinnedNode>`), and traversal reads
+//! go through `Pin<&Self>`. Because a pinned node can never move, the raw
+//! pointers stay valid for as long as the nodes live.
+
+use std::{marker::PhantomPinned, pin::Pin, ptr::null};
+
+/// A node in an intrusive doubly-linked list.
+///
+/// A node is inert until it has been pinned and linked to its neighbours; the
+/// linking and traversal APIs are only callable through `Pin`.
+#[derive(Debug)]
+pub struct PinnedNode {
+    /// the payload carried by this node
+    value: usize,
+    /// pointer to the next node, or null at the tail
+    next: *const PinnedNode,
+    /// pointer to the previous node, or null at the head
+    prev: *const PinnedNode,
+    /// a linked node must never move, or its neighbours' pointers would dangle
+    _pin: PhantomPinned,
+}
+impl PinnedNode {
+    /// Return a new, unlinked node.
+    pub fn new(value: usize) -> Self {
+        Self {
+            value,
+            next: null(),
+            prev: null(),
+            _pin: PhantomPinned,
+        }
+    }
+
+    /// The payload of this node.
+    pub fn value(self: Pin<&Self>) -> usize {
+        self.value
+    }
+
+    /// Splice `other` in directly after `self`.
+    ///
+    /// Both nodes must already be pinned, which is how we know neither will
+    /// move out from under the raw pointers we are about to store. Any node
+    /// that previously followed `self` becomes the successor of `other`.
+    pub fn push_pinned(self: Pin<&mut Self>, other: Pin<&mut PinnedNode>) {
+        // SAFETY: we only rewrite pointer fields in place; we never move the
+        // nodes, and both are pinned so their addresses are stable.
+        let this = unsafe { self.get_unchecked_mut() };
+        let other = unsafe { other.get_unchecked_mut() };
+
+        other.next = this.next;
+        other.prev = this as *const PinnedNode;
+        if let Some(following) = unsafe { (this.next as *mut PinnedNode).as_mut() } {
+            following.prev = other as *const PinnedNode;
+        }
+        this.next = other as *const PinnedNode;
+    }
+
+    /// The next node in the list, if any.
+    pub fn next(self: Pin<&Self>) -> Option<Pin<&PinnedNode>> {
+        // SAFETY: `next` either is null or points at a pinned node that
+        // outlives this traversal, so re-pinning the shared reference is sound.
+        unsafe { self.next.as_ref().map(|n| Pin::new_unchecked(n)) }
+    }
+
+    /// The previous node in the list, if any.
+    pub fn prev(self: Pin<&Self>) -> Option<Pin<&PinnedNode>> {
+        // SAFETY: see [`next`](Self::next).
+        unsafe { self.prev.as_ref().map(|n| Pin::new_unchecked(n)) }
+    }
+
+    /// Collect the values from this node to the tail, following `next`.
+    pub fn collect_forward(self: Pin<&Self>) -> Vec<usize> {
+        let mut out = vec![self.value()];
+        let mut cur = self.next();
+        while let Some(node) = cur {
+            out.push(node.value());
+            cur = node.next();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_traverse() {
+        let mut a = Box::pin(PinnedNode::new(1));
+        let mut b = Box::pin(PinnedNode::new(2));
+        let mut c = Box::pin(PinnedNode::new(3));
+
+        // Link a -> b, then splice c between a and b: a -> c -> b.
+        a.as_mut().push_pinned(b.as_mut());
+        a.as_mut().push_pinned(c.as_mut());
+
+        assert_eq!(a.as_ref().collect_forward(), vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_back_links() {
+        let mut a = Box::pin(PinnedNode::new(10));
+        let mut b = Box::pin(PinnedNode::new(20));
+
+        a.as_mut().push_pinned(b.as_mut());
+
+        let second = a.as_ref().next().unwrap();
+        assert_eq!(second.value(), 20);
+        assert_eq!(second.prev().unwrap().value(), 10);
+    }
+}
@@ -4,22 +4,32 @@
 
 use std::{marker::PhantomPinned, pin::Pin, ptr::null};
 
+pub mod future;
+pub mod intrusive;
+pub mod projection;
+
 /// Playing with self-references and pin
 #[derive(Debug)]
-pub struct SelfRefer {
+pub struct SelfRefer<T> {
     /// a pointer to `v`
-    ptr: *const usize,
+    ptr: *const T,
     /// the value to be checked against to make sure `ptr` is properly set
-    v: usize,
+    v: T,
+    /// bumped on every in-place mutation of `v`
+    generation: usize,
+    /// the `generation` captured when `ptr` was last wired up, if ever
+    wired_generation: Option<usize>,
     /// make sure `v` won't be moved and thus `ptr` always valid
     _pin: PhantomPinned,
 }
-impl SelfRefer {
+impl<T> SelfRefer<T> {
     /// Return a new instance without self-referencing yet
-    pub fn new(v: usize) -> Self {
+    pub fn new(v: T) -> Self {
         Self {
             v,
             ptr: null(),
+            generation: 0,
+            wired_generation: None,
             _pin: PhantomPinned,
         }
     }
@@ -30,25 +40,99 @@ impl SelfRefer {
         let this = unsafe { self.get_unchecked_mut() };
 
         this.ptr = &this.v as _;
+        this.wired_generation = Some(this.generation);
     }
 
     /// Should be pinned before use to make sure the self reference is correct
-    pub fn referred(self: Pin<&Self>) -> Option<usize> {
-        unsafe { self.ptr.as_ref() }.copied()
+    pub fn referred(self: Pin<&Self>) -> Option<&T> {
+        unsafe { self.get_ref().ptr.as_ref() }
     }
 
-    pub fn set(&mut self, v: usize) {
+    pub fn set(&mut self, v: T) {
         self.v = v;
+        self.generation += 1;
     }
 
     /// Being pinned does not mean you can't change the value in-place.
     /// It is just that you can't move the whole memory slot elsewhere.
-    pub fn pinned_set(self: Pin<&mut Self>, v: usize) {
+    pub fn pinned_set(self: Pin<&mut Self>, v: T) {
         let this = unsafe { self.get_unchecked_mut() };
 
         this.v = v;
+        this.generation += 1;
+    }
+
+    /// Whether the value has been mutated in place since the self reference was
+    /// last wired up by [`refer_self`].
+    ///
+    /// The self pointer itself stays valid across an in-place [`set`], but a
+    /// bumped generation lets callers tell that the referred value is no longer
+    /// the one observed when the pointer was established.
+    ///
+    /// [`refer_self`]: Self::refer_self
+    /// [`set`]: Self::set
+    pub fn is_stale(self: Pin<&Self>) -> bool {
+        match self.get_ref().wired_generation {
+            Some(wired) => wired != self.generation,
+            None => false,
+        }
+    }
+
+    /// Heap-allocate, pin, and wire up the self reference in one step.
+    ///
+    /// This hands back an already-valid pinned value so callers never have to
+    /// touch `Pin::new_unchecked` or remember to call [`refer_self`] themselves.
+    /// [`referred`] is guaranteed to return `Some(&v)` right after construction.
+    ///
+    /// [`refer_self`]: Self::refer_self
+    /// [`referred`]: Self::referred
+    pub fn boxed_pinned(v: T) -> Pin<Box<Self>> {
+        let mut boxed = Box::pin(Self::new(v));
+        boxed.as_mut().refer_self();
+        boxed
     }
 }
+/// Enforce the pin drop guarantee: a pinned value's memory must stay valid and
+/// in place until its `Drop` runs. If the self pointer was ever wired up it
+/// must still point at our own `v`; if it doesn't, the value was moved or its
+/// memory stomped while pinned, which is undefined behaviour this check surfaces
+/// in debug builds.
+impl<T> Drop for SelfRefer<T> {
+    fn drop(&mut self) {
+        debug_assert!(
+            self.ptr.is_null() || std::ptr::eq(self.ptr, &self.v),
+            "SelfRefer self-pointer no longer points at its own value: \
+             the pinned value was moved or its memory invalidated, \
+             violating the drop guarantee"
+        );
+    }
+}
+
+/// Pin a freshly built [`SelfRefer`] to the stack and wire up its self
+/// reference, binding the result as `Pin<&mut SelfRefer>`.
+///
+/// Like [`boxed_pinned`] but without the heap allocation: the value lives in
+/// the caller's frame and cannot be moved afterwards, so [`referred`] is valid
+/// as soon as the macro returns.
+///
+/// ```
+/// # use test_pin::{pinned_on_stack, SelfRefer};
+/// pinned_on_stack!(sr, 42);
+/// assert_eq!(sr.as_ref().referred(), Some(&42));
+/// ```
+///
+/// [`boxed_pinned`]: SelfRefer::boxed_pinned
+/// [`referred`]: SelfRefer::referred
+#[macro_export]
+macro_rules! pinned_on_stack {
+    ($name:ident, $v:expr) => {
+        let mut $name = $crate::SelfRefer::new($v);
+        // SAFETY: `$name` is shadowed below so the unpinned binding can no
+        // longer be touched, and the pinned binding drops in place.
+        let mut $name = unsafe { core::pin::Pin::new_unchecked(&mut $name) };
+        $name.as_mut().refer_self();
+    };
+}
 
 #[cfg(test)]
 mod tests {
@@ -56,7 +140,7 @@ mod tests {
 
     #[test]
     fn test_pin() {
-        let v = 42;
+        let v = 42usize;
         let mut sr = SelfRefer::new(v);
 
         // Force `sr` to be moved
@@ -70,6 +154,41 @@ mod tests {
         assert_eq!(p.as_ref().referred(), None);
 
         p.as_mut().refer_self();
-        assert_eq!(p.as_ref().referred(), Some(v));
+        assert_eq!(p.as_ref().referred(), Some(&v));
+    }
+
+    #[test]
+    fn test_boxed_pinned() {
+        let sr = SelfRefer::boxed_pinned(42);
+        assert_eq!(sr.as_ref().referred(), Some(&42));
+    }
+
+    #[test]
+    fn test_pinned_on_stack() {
+        pinned_on_stack!(sr, 42);
+        assert_eq!(sr.as_ref().referred(), Some(&42));
+    }
+
+    #[test]
+    fn test_staleness_tracks_in_place_set() {
+        pinned_on_stack!(sr, 42usize);
+        assert!(!sr.as_ref().is_stale());
+
+        sr.as_mut().pinned_set(420);
+        // The pointer still resolves, but the value changed under it.
+        assert_eq!(sr.as_ref().referred(), Some(&420));
+        assert!(sr.as_ref().is_stale());
+
+        // Re-wiring captures the current generation again.
+        sr.as_mut().refer_self();
+        assert!(!sr.as_ref().is_stale());
+    }
+
+    #[test]
+    fn test_drop_guarantee_holds_for_valid_pinning() {
+        // A correctly pinned value drops without tripping the guarantee check.
+        let sr = SelfRefer::boxed_pinned(42usize);
+        assert_eq!(sr.as_ref().referred(), Some(&42));
+        drop(sr);
     }
 }
@@ -0,0 +1,86 @@
+//! Hand-written structural-pinning projections, following the std docs'
+//! "projections and structural pinning" section.
+//!
+//! A [`Composite`] bundles a `!Unpin` [`SelfRefer`] with a freely movable
+//! [`String`]. Projecting `Pin<&mut Composite>` onto its fields has to respect
+//! two opposite rules:
+//!
+//! - [`pinned`](Composite::project_pinned) is **structural**: the inner
+//!   `SelfRefer` is `!Unpin`, so the projection hands back a `Pin<&mut _>` and
+//!   we must never move out of the field.
+//! - [`movable`](Composite::project_movable) is **non-structural**: the
+//!   `String` does not care about pinning, so the projection hands back a plain
+//!   `&mut String` and we must never offer a `Pin` to it.
+
+use std::pin::Pin;
+
+use crate::SelfRefer;
+
+/// A value with one structurally-pinned field and one movable field.
+#[derive(Debug)]
+pub struct Composite {
+    /// structurally pinned: `!Unpin`, projected as `Pin<&mut SelfRefer<usize>>`
+    pinned: SelfRefer<usize>,
+    /// not structurally pinned: freely movable, projected as `&mut String`
+    movable: String,
+}
+impl Composite {
+    /// Build a composite whose pinned field has not been self-referenced yet.
+    pub fn new(v: usize, movable: String) -> Self {
+        Self {
+            pinned: SelfRefer::new(v),
+            movable,
+        }
+    }
+
+    /// Structural projection onto the pinned field.
+    ///
+    /// Because `SelfRefer<usize>` is `!Unpin`, pinning the composite pins this
+    /// field too, so it is sound to hand out `Pin<&mut SelfRefer<usize>>`. The
+    /// caller must not move out of it.
+    pub fn project_pinned(self: Pin<&mut Self>) -> Pin<&mut SelfRefer<usize>> {
+        // SAFETY: `pinned` is a structurally-pinned field — we never move out
+        // of it and we re-pin the reference we return.
+        unsafe { self.map_unchecked_mut(|s| &mut s.pinned) }
+    }
+
+    /// Non-structural projection onto the movable field.
+    ///
+    /// The `String` does not rely on the pin invariant, so it is sound to hand
+    /// out a plain `&mut String`. We must never expose a `Pin` to it.
+    pub fn project_movable(self: Pin<&mut Self>) -> &mut String {
+        // SAFETY: `movable` is not structurally pinned; handing out `&mut` to a
+        // non-structural field of a pinned value is allowed.
+        unsafe { &mut self.get_unchecked_mut().movable }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_pinned_is_structural() {
+        let mut c = Box::pin(Composite::new(42, "hello".to_owned()));
+
+        // The pinned field is only valid as a self reference once wired up
+        // through its pinned projection.
+        assert_eq!(c.as_mut().project_pinned().as_ref().referred(), None);
+        c.as_mut().project_pinned().refer_self();
+        assert_eq!(c.as_mut().project_pinned().as_ref().referred(), Some(&42));
+    }
+
+    #[test]
+    fn test_project_movable_is_non_structural() {
+        let mut c = Box::pin(Composite::new(0, "hello".to_owned()));
+
+        // The movable field can be mutated, and even replaced, through a plain
+        // `&mut` without disturbing the pinned field.
+        c.as_mut().project_movable().push_str(", world");
+        assert_eq!(c.as_mut().project_movable(), "hello, world");
+
+        c.as_mut().project_pinned().refer_self();
+        *c.as_mut().project_movable() = "replaced".to_owned();
+        assert_eq!(c.as_mut().project_pinned().as_ref().referred(), Some(&0));
+    }
+}